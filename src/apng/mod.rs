@@ -0,0 +1,367 @@
+//! Animated PNG (APNG) support: the `acTL`, `fcTL` and `fdAT` control
+//! chunks and an assembler that weaves them into a base image to produce a
+//! valid APNG stream.
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::png::Png;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Converts a byte slice to one of size 4.
+fn convert_slice_to_fixed(arr: &[u8]) -> [u8; 4] {
+    arr.try_into().expect("Slice with incorrect length")
+}
+
+/// The animation control chunk (`acTL`): how many frames the animation has
+/// and how many times it should play (0 meaning loop forever).
+#[derive(PartialEq, Debug)]
+pub struct AnimationControl {
+    pub num_frames: u32,
+    pub num_plays: u32,
+}
+
+impl AnimationControl {
+    pub fn new(num_frames: u32, num_plays: u32) -> AnimationControl {
+        AnimationControl {
+            num_frames,
+            num_plays,
+        }
+    }
+
+    /// Serializes the 8-byte `acTL` field layout.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(8);
+        bytes.extend_from_slice(&self.num_frames.to_be_bytes());
+        bytes.extend_from_slice(&self.num_plays.to_be_bytes());
+        bytes
+    }
+
+    /// Parses an `acTL` payload, rejecting a zero frame count.
+    pub fn from_bytes(data: &[u8]) -> Result<AnimationControl, String> {
+        if data.len() != 8 {
+            return Err("acTL payload must be 8 bytes".to_string());
+        }
+        let num_frames = u32::from_be_bytes(convert_slice_to_fixed(&data[0..4]));
+        let num_plays = u32::from_be_bytes(convert_slice_to_fixed(&data[4..8]));
+        if num_frames == 0 {
+            return Err("acTL num_frames must be at least 1".to_string());
+        }
+        Ok(AnimationControl {
+            num_frames,
+            num_plays,
+        })
+    }
+
+    /// Wraps this control block in its `acTL` chunk.
+    pub fn to_chunk(&self) -> Chunk {
+        Chunk::new(ChunkType::from_str("acTL").unwrap(), self.as_bytes())
+    }
+}
+
+/// The frame control chunk (`fcTL`): geometry, timing and compositing for a
+/// single animation frame.
+#[derive(PartialEq, Debug)]
+pub struct FrameControl {
+    pub sequence_number: u32,
+    pub width: u32,
+    pub height: u32,
+    pub x_offset: u32,
+    pub y_offset: u32,
+    pub delay_num: u16,
+    pub delay_den: u16,
+    pub dispose_op: u8,
+    pub blend_op: u8,
+}
+
+impl FrameControl {
+    /// Serializes the 26-byte `fcTL` field layout.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(26);
+        bytes.extend_from_slice(&self.sequence_number.to_be_bytes());
+        bytes.extend_from_slice(&self.width.to_be_bytes());
+        bytes.extend_from_slice(&self.height.to_be_bytes());
+        bytes.extend_from_slice(&self.x_offset.to_be_bytes());
+        bytes.extend_from_slice(&self.y_offset.to_be_bytes());
+        bytes.extend_from_slice(&self.delay_num.to_be_bytes());
+        bytes.extend_from_slice(&self.delay_den.to_be_bytes());
+        bytes.push(self.dispose_op);
+        bytes.push(self.blend_op);
+        bytes
+    }
+
+    /// Parses an `fcTL` payload, validating the dimensions and the
+    /// dispose/blend operation ranges (0..=2 and 0..=1 respectively).
+    pub fn from_bytes(data: &[u8]) -> Result<FrameControl, String> {
+        if data.len() != 26 {
+            return Err("fcTL payload must be 26 bytes".to_string());
+        }
+        let width = u32::from_be_bytes(convert_slice_to_fixed(&data[4..8]));
+        let height = u32::from_be_bytes(convert_slice_to_fixed(&data[8..12]));
+        if width == 0 || height == 0 {
+            return Err("fcTL width and height must be non-zero".to_string());
+        }
+        let dispose_op = data[24];
+        let blend_op = data[25];
+        if dispose_op > 2 {
+            return Err("fcTL dispose_op must be in 0..=2".to_string());
+        }
+        if blend_op > 1 {
+            return Err("fcTL blend_op must be in 0..=1".to_string());
+        }
+        Ok(FrameControl {
+            sequence_number: u32::from_be_bytes(convert_slice_to_fixed(&data[0..4])),
+            width,
+            height,
+            x_offset: u32::from_be_bytes(convert_slice_to_fixed(&data[12..16])),
+            y_offset: u32::from_be_bytes(convert_slice_to_fixed(&data[16..20])),
+            delay_num: u16::from_be_bytes(data[20..22].try_into().unwrap()),
+            delay_den: u16::from_be_bytes(data[22..24].try_into().unwrap()),
+            dispose_op,
+            blend_op,
+        })
+    }
+
+    /// Wraps this control block in its `fcTL` chunk.
+    pub fn to_chunk(&self) -> Chunk {
+        Chunk::new(ChunkType::from_str("fcTL").unwrap(), self.as_bytes())
+    }
+}
+
+/// The frame data chunk (`fdAT`): a sequence number followed by the raw
+/// frame image bytes (the `fdAT` counterpart of `IDAT`).
+#[derive(PartialEq, Debug)]
+pub struct FrameData {
+    pub sequence_number: u32,
+    pub data: Vec<u8>,
+}
+
+impl FrameData {
+    pub fn new(sequence_number: u32, data: Vec<u8>) -> FrameData {
+        FrameData {
+            sequence_number,
+            data,
+        }
+    }
+
+    /// Serializes the `fdAT` field layout: sequence number then frame bytes.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(4 + self.data.len());
+        bytes.extend_from_slice(&self.sequence_number.to_be_bytes());
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    /// Parses an `fdAT` payload (a 4-byte sequence number plus frame bytes).
+    pub fn from_bytes(data: &[u8]) -> Result<FrameData, String> {
+        if data.len() < 4 {
+            return Err("fdAT payload must be at least 4 bytes".to_string());
+        }
+        Ok(FrameData {
+            sequence_number: u32::from_be_bytes(convert_slice_to_fixed(&data[0..4])),
+            data: data[4..].to_vec(),
+        })
+    }
+
+    /// Wraps this frame data in its `fdAT` chunk.
+    pub fn to_chunk(&self) -> Chunk {
+        Chunk::new(ChunkType::from_str("fdAT").unwrap(), self.as_bytes())
+    }
+}
+
+/// A single animation frame: its control metadata plus, for frames other
+/// than the default image, the raw frame bytes. The default (first) frame
+/// reuses the base image's `IDAT`, so it carries no data of its own.
+pub struct Frame {
+    pub control: FrameControl,
+    pub data: Option<Vec<u8>>,
+}
+
+/// Assembles an APNG from a base image and a list of frames.
+///
+/// The `acTL` chunk is inserted immediately after `IHDR`; the first frame's
+/// `fcTL` precedes the existing `IDAT` (the default image), and every later
+/// frame contributes an `fcTL`/`fdAT` pair. Sequence numbers increase
+/// monotonically across every `fcTL` and `fdAT` chunk, starting at 0.
+pub fn assemble(base: &Png, num_plays: u32, frames: Vec<Frame>) -> Result<Png, String> {
+    if frames.is_empty() {
+        return Err("An APNG needs at least one frame".to_string());
+    }
+    let input = base.chunks();
+    if !input.iter().any(|chunk| chunk.chunk_type().bytes() == *b"IHDR") {
+        return Err("Base image is missing its IHDR chunk".to_string());
+    }
+    let first_idat = input
+        .iter()
+        .position(|chunk| chunk.chunk_type().bytes() == *b"IDAT")
+        .ok_or("Base image is missing its IDAT chunk")?;
+    let last_idat = input
+        .iter()
+        .rposition(|chunk| chunk.chunk_type().bytes() == *b"IDAT")
+        .unwrap();
+
+    let mut frames = frames.into_iter();
+    let mut first = frames.next().unwrap();
+    if first.data.is_some() {
+        return Err("The first frame must reuse the base IDAT and carry no data".to_string());
+    }
+
+    // Preserve every chunk of the base image, threading the animation
+    // control chunks through at the spec-mandated positions.
+    let mut chunks: Vec<Chunk> = Vec::with_capacity(input.len() + 2 + 2 * frames.len());
+    let mut sequence_number: u32 = 0;
+    let mut inserted_actl = false;
+    for (index, chunk) in input.iter().enumerate() {
+        // The default image's fcTL comes immediately before the first IDAT.
+        if index == first_idat {
+            first.control.sequence_number = sequence_number;
+            sequence_number += 1;
+            chunks.push(first.control.to_chunk());
+        }
+        chunks.push(clone_chunk(chunk));
+        // acTL is inserted right after IHDR (hence before any IDAT).
+        if !inserted_actl && chunk.chunk_type().bytes() == *b"IHDR" {
+            chunks.push(AnimationControl::new(1 + frames.len() as u32, num_plays).to_chunk());
+            inserted_actl = true;
+        }
+        // Later frames' fcTL/fdAT pairs follow the last IDAT.
+        if index == last_idat {
+            for mut frame in frames.by_ref() {
+                let data = frame
+                    .data
+                    .take()
+                    .ok_or("Frames after the first must carry their own data")?;
+                frame.control.sequence_number = sequence_number;
+                sequence_number += 1;
+                chunks.push(frame.control.to_chunk());
+                chunks.push(FrameData::new(sequence_number, data).to_chunk());
+                sequence_number += 1;
+            }
+        }
+    }
+
+    Ok(Png::from_chunks(chunks))
+}
+
+/// Rebuilds a chunk from a borrowed one, copying its type and data.
+fn clone_chunk(chunk: &Chunk) -> Chunk {
+    let chunk_type = ChunkType::try_from(chunk.chunk_type().bytes()).unwrap();
+    Chunk::new(chunk_type, chunk.data().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+    use crate::png::Png;
+
+    fn frame_control() -> FrameControl {
+        FrameControl {
+            sequence_number: 0,
+            width: 4,
+            height: 4,
+            x_offset: 0,
+            y_offset: 0,
+            delay_num: 1,
+            delay_den: 10,
+            dispose_op: 0,
+            blend_op: 0,
+        }
+    }
+
+    fn base_png() -> Png {
+        Png::from_chunks(vec![
+            Chunk::new(ChunkType::from_str("IHDR").unwrap(), vec![0; 13]),
+            Chunk::new(ChunkType::from_str("IDAT").unwrap(), vec![1, 2, 3]),
+            Chunk::new(ChunkType::from_str("IEND").unwrap(), Vec::new()),
+        ])
+    }
+
+    #[test]
+    pub fn test_animation_control_round_trip() {
+        let actl = AnimationControl::new(3, 0);
+        assert_eq!(actl.as_bytes().len(), 8);
+        assert_eq!(AnimationControl::from_bytes(&actl.as_bytes()).unwrap(), actl);
+    }
+
+    #[test]
+    pub fn test_animation_control_rejects_zero_frames() {
+        let bytes = [0u8; 8];
+        assert!(AnimationControl::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    pub fn test_frame_control_round_trip() {
+        let fctl = frame_control();
+        assert_eq!(fctl.as_bytes().len(), 26);
+        assert_eq!(FrameControl::from_bytes(&fctl.as_bytes()).unwrap(), fctl);
+    }
+
+    #[test]
+    pub fn test_frame_control_rejects_bad_blend_op() {
+        let mut fctl = frame_control();
+        fctl.blend_op = 2;
+        assert!(FrameControl::from_bytes(&fctl.as_bytes()).is_err());
+    }
+
+    #[test]
+    pub fn test_frame_data_round_trip() {
+        let fdat = FrameData::new(7, vec![9, 8, 7]);
+        assert_eq!(FrameData::from_bytes(&fdat.as_bytes()).unwrap(), fdat);
+    }
+
+    #[test]
+    pub fn test_assemble_inserts_actl_after_ihdr() {
+        let base = base_png();
+        let frames = vec![
+            Frame {
+                control: frame_control(),
+                data: None,
+            },
+            Frame {
+                control: frame_control(),
+                data: Some(vec![4, 5, 6]),
+            },
+        ];
+        let apng = assemble(&base, 0, frames).unwrap();
+        let types: Vec<String> = apng
+            .chunks()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(
+            types,
+            vec!["IHDR", "acTL", "fcTL", "IDAT", "fcTL", "fdAT", "IEND"]
+        );
+    }
+
+    #[test]
+    pub fn test_assemble_sequence_numbers_are_monotonic() {
+        let base = base_png();
+        let frames = vec![
+            Frame {
+                control: frame_control(),
+                data: None,
+            },
+            Frame {
+                control: frame_control(),
+                data: Some(vec![4, 5, 6]),
+            },
+        ];
+        let apng = assemble(&base, 0, frames).unwrap();
+        // fcTL #0 = seq 0, fcTL #1 = seq 1, fdAT = seq 2.
+        let fctls: Vec<u32> = apng
+            .chunks()
+            .iter()
+            .filter(|chunk| chunk.chunk_type().to_string() == "fcTL")
+            .map(|chunk| u32::from_be_bytes(convert_slice_to_fixed(&chunk.data()[0..4])))
+            .collect();
+        assert_eq!(fctls, vec![0, 1]);
+        let fdat = apng.chunk_by_type("fdAT").unwrap();
+        assert_eq!(
+            u32::from_be_bytes(convert_slice_to_fixed(&fdat.data()[0..4])),
+            2
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use crate::chunk_type::ChunkType;
+use std::convert::TryFrom;
+use std::sync::OnceLock;
+
+/// Converts a byte slice to one of size 4.
+fn convert_slice_to_fixed(arr: &[u8]) -> [u8; 4] {
+    arr.try_into().expect("Slice with incorrect length")
+}
+
+/// Lazily built CRC-32 lookup table using the reflected polynomial 0xEDB88320.
+/// `table[n]` is `n` run through the 8-bit reflected CRC update.
+fn crc_table() -> &'static [u32; 256] {
+    static TABLE: OnceLock<[u32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u32; 256];
+        for (n, entry) in table.iter_mut().enumerate() {
+            let mut c = n as u32;
+            for _ in 0..8 {
+                c = if c & 1 != 0 {
+                    0xEDB88320 ^ (c >> 1)
+                } else {
+                    c >> 1
+                };
+            }
+            *entry = c;
+        }
+        table
+    })
+}
+
+/// Computes the PNG CRC-32 over a byte sequence as the spec requires.
+fn crc32(bytes: &[u8]) -> u32 {
+    let table = crc_table();
+    let mut crc: u32 = 0xFFFFFFFF;
+    for &byte in bytes {
+        crc = table[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc ^ 0xFFFFFFFF
+}
+
+/// A complete PNG chunk: a 4-byte big-endian length, a 4-byte type,
+/// the data bytes, and a 4-byte big-endian CRC computed over the type
+/// and data bytes.
+#[derive(Debug)]
+pub struct Chunk {
+    chunk_type: ChunkType,
+    data: Vec<u8>,
+}
+
+/// methods
+impl Chunk {
+    /// Create a chunk from a type and its data bytes.
+    pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Chunk {
+        Chunk { chunk_type, data }
+    }
+
+    /// The length of the data portion, as stored in the chunk header.
+    pub fn length(&self) -> u32 {
+        self.data.len() as u32
+    }
+
+    /// The chunk type of this chunk.
+    pub fn chunk_type(&self) -> &ChunkType {
+        &self.chunk_type
+    }
+
+    /// The data bytes carried by the chunk.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The CRC-32 over the chunk-type bytes followed by the data bytes.
+    pub fn crc(&self) -> u32 {
+        let mut checked: Vec<u8> = Vec::with_capacity(4 + self.data.len());
+        checked.extend_from_slice(&self.chunk_type.bytes());
+        checked.extend_from_slice(&self.data);
+        crc32(&checked)
+    }
+
+    /// Interprets the data bytes as a UTF-8 string.
+    pub fn data_as_string(&self) -> Result<String, String> {
+        match String::from_utf8(self.data.clone()) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(format!("Invalid byte sequence for UTF-8 sequence {}", e)),
+        }
+    }
+
+    /// Serializes the full chunk: length, type, data, and CRC.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(12 + self.data.len());
+        bytes.extend_from_slice(&self.length().to_be_bytes());
+        bytes.extend_from_slice(&self.chunk_type.bytes());
+        bytes.extend_from_slice(&self.data);
+        bytes.extend_from_slice(&self.crc().to_be_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for Chunk {
+    type Error = String;
+
+    /// Parses a chunk from raw bytes, rejecting chunks whose stored CRC
+    /// doesn't match the one computed over the type and data bytes.
+    fn try_from(bytes: &[u8]) -> Result<Chunk, String> {
+        if bytes.len() < 12 {
+            return Err("Chunk must be at least 12 bytes long".to_string());
+        }
+        let length = u32::from_be_bytes(convert_slice_to_fixed(&bytes[0..4])) as usize;
+        match length.checked_add(12) {
+            Some(total) if bytes.len() >= total => {}
+            _ => return Err("Chunk data is shorter than its declared length".to_string()),
+        }
+        let chunk_type = ChunkType::try_from(convert_slice_to_fixed(&bytes[4..8]))?;
+        let data = bytes[8..8 + length].to_vec();
+        let stored_crc = u32::from_be_bytes(convert_slice_to_fixed(
+            &bytes[8 + length..12 + length],
+        ));
+
+        let chunk = Chunk::new(chunk_type, data);
+        if chunk.crc() != stored_crc {
+            return Err("Chunk CRC does not match its contents".to_string());
+        }
+        Ok(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk_type::ChunkType;
+    use std::convert::TryFrom;
+
+    fn testing_chunk() -> Chunk {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656334;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        Chunk::try_from(chunk_data.as_ref()).unwrap()
+    }
+
+    #[test]
+    pub fn test_new_chunk() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let data = "This is where your secret message will be!".as_bytes().to_vec();
+        let chunk = Chunk::new(chunk_type, data);
+        assert_eq!(chunk.length(), 42);
+        assert_eq!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    pub fn test_chunk_length() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.length(), 42);
+    }
+
+    #[test]
+    pub fn test_chunk_type() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.chunk_type().to_string(), "RuSt");
+    }
+
+    #[test]
+    pub fn test_chunk_string() {
+        let chunk = testing_chunk();
+        let chunk_string = chunk.data_as_string().unwrap();
+        let expected = "This is where your secret message will be!".to_string();
+        assert_eq!(chunk_string, expected);
+    }
+
+    #[test]
+    pub fn test_chunk_crc() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    pub fn test_valid_chunk_from_bytes() {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656334;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from(chunk_data.as_ref()).unwrap();
+        let chunk_string = chunk.data_as_string().unwrap();
+        let expected = "This is where your secret message will be!".to_string();
+
+        assert_eq!(chunk.length(), 42);
+        assert_eq!(chunk.chunk_type().to_string(), "RuSt");
+        assert_eq!(chunk_string, expected);
+        assert_eq!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    pub fn test_invalid_chunk_from_bytes() {
+        let data_length: u32 = 42;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656333;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from(chunk_data.as_ref());
+        assert!(chunk.is_err());
+    }
+}
@@ -1,40 +1,90 @@
+use std::convert::TryFrom;
 use std::convert::TryInto;
-
-/// Converts a byte slice to one of size 4.
-fn convert_slice_to_fixed(arr: &[u8]) -> [u8; 4] {
-    arr.try_into().expect("Slice with incorrect length")
-}
+use std::fmt;
+use std::str::FromStr;
 
 /// Struct to implement a chunk type encoding for PNGs
 /// 4-byte consisting of only uppercase and lowercase ASCII letters
 /// (A-Z and a-z, or 65-90 and 97-122 decimal)
-#[derive(PartialEq, Debug)]
-struct ChunkType {
+#[derive(PartialEq)]
+pub struct ChunkType {
     _container: [u8; 4],
 }
 
-/// methods
-impl ChunkType {
-    /// Create from static byte array
-    pub fn try_from(arr: [u8; 4]) -> Result<ChunkType, String> {
-        for &byte_val in arr.iter() {
-            // Check valid ascii value
-            if byte_val < 65 || (byte_val > 90 && byte_val < 97) || byte_val > 122 {
-                return Err(
-                    "Chunk type encoding must be in ascii lowercase/upper case (65-90/97-122)"
-                        .to_string(),
-                );
+/// The ways a chunk type can fail to parse from raw bytes or text.
+#[derive(PartialEq, Eq, Debug)]
+pub enum ChunkTypeError {
+    /// The input was not exactly four bytes long.
+    InvalidLength { got: usize },
+    /// A byte fell outside the ASCII letter range (A-Z / a-z).
+    InvalidByte { byte: u8, position: usize },
+}
+
+impl fmt::Display for ChunkTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkTypeError::InvalidLength { got } => {
+                write!(f, "Chunk type must be exactly 4 bytes, got {}", got)
+            }
+            ChunkTypeError::InvalidByte { byte, position } => write!(
+                f,
+                "Chunk type byte {} at position {} is not an ASCII letter (65-90/97-122)",
+                byte, position
+            ),
+        }
+    }
+}
+
+/// Bridges the typed error back to the crate's pervasive `String` errors so
+/// callers can keep propagating with `?` into `Result<_, String>`.
+impl From<ChunkTypeError> for String {
+    fn from(err: ChunkTypeError) -> String {
+        err.to_string()
+    }
+}
+
+impl TryFrom<[u8; 4]> for ChunkType {
+    type Error = ChunkTypeError;
+
+    fn try_from(arr: [u8; 4]) -> Result<ChunkType, ChunkTypeError> {
+        for (position, &byte) in arr.iter().enumerate() {
+            if !byte.is_ascii_alphabetic() {
+                return Err(ChunkTypeError::InvalidByte { byte, position });
             }
         }
-        let c = ChunkType { _container: arr };
-        Ok(c)
+        Ok(ChunkType { _container: arr })
+    }
+}
+
+impl FromStr for ChunkType {
+    type Err = ChunkTypeError;
+
+    fn from_str(input_str: &str) -> Result<ChunkType, ChunkTypeError> {
+        let bytes = input_str.as_bytes();
+        let arr: [u8; 4] = bytes
+            .try_into()
+            .map_err(|_| ChunkTypeError::InvalidLength { got: bytes.len() })?;
+        ChunkType::try_from(arr)
+    }
+}
+
+impl fmt::Display for ChunkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self._container.iter() {
+            write!(f, "{}", byte as char)?;
+        }
+        Ok(())
     }
-    /// Create from &str slice
-    pub fn from_str(input_str: &str) -> Result<ChunkType, String> {
-        let res: ChunkType = ChunkType::try_from(convert_slice_to_fixed(input_str.as_bytes()))?;
+}
 
-        Ok(res)
+impl fmt::Debug for ChunkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChunkType({})", self)
     }
+}
+
+/// methods
+impl ChunkType {
     /// Returns the actual bytes of the Chunk Type
     pub fn bytes(&self) -> [u8; 4] {
         self._container
@@ -71,14 +121,136 @@ impl ChunkType {
     }
 }
 
-impl ToString for ChunkType {
-    fn to_string(&self) -> String {
-        let s = match String::from_utf8(self._container.to_vec()) {
-            Ok(v) => v,
-            Err(e) => panic!("Invalid byte sequence for UTF-8 sequence {}", e),
+/// The standard PNG chunk types this crate recognises.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum StandardChunkType {
+    Ihdr,
+    Plte,
+    Idat,
+    Iend,
+    Trns,
+    Bkgd,
+    Time,
+    Phys,
+    Chrm,
+    Gama,
+    Srgb,
+    Iccp,
+    Text,
+    Ztxt,
+    Itxt,
+}
+
+impl StandardChunkType {
+    /// Looks a raw 4-byte type up in the registry of standard chunks.
+    pub fn from_bytes(bytes: &[u8; 4]) -> Option<StandardChunkType> {
+        let found = match bytes {
+            b"IHDR" => StandardChunkType::Ihdr,
+            b"PLTE" => StandardChunkType::Plte,
+            b"IDAT" => StandardChunkType::Idat,
+            b"IEND" => StandardChunkType::Iend,
+            b"tRNS" => StandardChunkType::Trns,
+            b"bKGD" => StandardChunkType::Bkgd,
+            b"tIME" => StandardChunkType::Time,
+            b"pHYs" => StandardChunkType::Phys,
+            b"cHRM" => StandardChunkType::Chrm,
+            b"gAMA" => StandardChunkType::Gama,
+            b"sRGB" => StandardChunkType::Srgb,
+            b"iCCP" => StandardChunkType::Iccp,
+            b"tEXt" => StandardChunkType::Text,
+            b"zTXt" => StandardChunkType::Ztxt,
+            b"iTXt" => StandardChunkType::Itxt,
+            _ => return None,
         };
-        s
+        Some(found)
+    }
+
+    /// Whether the chunk carries human-readable text (`tEXt`/`zTXt`/`iTXt`).
+    pub fn is_textual(&self) -> bool {
+        matches!(
+            self,
+            StandardChunkType::Text | StandardChunkType::Ztxt | StandardChunkType::Itxt
+        )
+    }
+}
+
+/// The semantic classification of a chunk type: critical or ancillary when
+/// it belongs to the standard registry, or unknown otherwise.
+#[derive(PartialEq, Debug)]
+pub enum ChunkClass {
+    /// A standard chunk that decoders must understand.
+    Critical(StandardChunkType),
+    /// A standard ancillary chunk (optional metadata, text, etc.).
+    Ancillary(StandardChunkType),
+    /// A chunk type not in the standard registry.
+    Unknown,
+}
+
+/// Named constants and semantic classification for the standard chunk types.
+impl ChunkType {
+    pub const IHDR: ChunkType = ChunkType { _container: *b"IHDR" };
+    pub const PLTE: ChunkType = ChunkType { _container: *b"PLTE" };
+    pub const IDAT: ChunkType = ChunkType { _container: *b"IDAT" };
+    pub const IEND: ChunkType = ChunkType { _container: *b"IEND" };
+    pub const TRNS: ChunkType = ChunkType { _container: *b"tRNS" };
+    pub const BKGD: ChunkType = ChunkType { _container: *b"bKGD" };
+    pub const TIME: ChunkType = ChunkType { _container: *b"tIME" };
+    pub const PHYS: ChunkType = ChunkType { _container: *b"pHYs" };
+    pub const CHRM: ChunkType = ChunkType { _container: *b"cHRM" };
+    pub const GAMA: ChunkType = ChunkType { _container: *b"gAMA" };
+    pub const SRGB: ChunkType = ChunkType { _container: *b"sRGB" };
+    pub const ICCP: ChunkType = ChunkType { _container: *b"iCCP" };
+    pub const TEXT: ChunkType = ChunkType { _container: *b"tEXt" };
+    pub const ZTXT: ChunkType = ChunkType { _container: *b"zTXt" };
+    pub const ITXT: ChunkType = ChunkType { _container: *b"iTXt" };
+
+    /// Classifies this chunk type by its standard meaning and property bits.
+    pub fn classify(&self) -> ChunkClass {
+        match StandardChunkType::from_bytes(&self._container) {
+            Some(standard) if self.is_critical() => ChunkClass::Critical(standard),
+            Some(standard) => ChunkClass::Ancillary(standard),
+            None => ChunkClass::Unknown,
+        }
+    }
+}
+
+/// Decodes a `tEXt` payload into its Latin-1 keyword and value strings.
+///
+/// The payload is a keyword, a null separator, and the text value; both
+/// halves are Latin-1, which maps one-to-one onto the first 256 Unicode
+/// code points.
+pub fn decode_text(data: &[u8]) -> Result<(String, String), String> {
+    let separator = data
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or("tEXt payload is missing its null separator")?;
+    let keyword = latin1_to_string(&data[..separator]);
+    let value = latin1_to_string(&data[separator + 1..]);
+    Ok((keyword, value))
+}
+
+/// Decodes a `zTXt` payload: a Latin-1 keyword, a null separator, a
+/// one-byte compression method, and a zlib-compressed value.
+pub fn decode_ztxt(data: &[u8]) -> Result<(String, String), String> {
+    let separator = data
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or("zTXt payload is missing its null separator")?;
+    let keyword = latin1_to_string(&data[..separator]);
+    let rest = &data[separator + 1..];
+    if rest.is_empty() {
+        return Err("zTXt payload is missing its compression method".to_string());
+    }
+    if rest[0] != 0 {
+        return Err("Unsupported zTXt compression method".to_string());
     }
+    let decompressed = crate::zlib::decompress(&rest[1..])?;
+    Ok((keyword, latin1_to_string(&decompressed)))
+}
+
+/// Interprets a byte sequence as Latin-1 text.
+fn latin1_to_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| byte as char).collect()
 }
 
 #[cfg(test)]
@@ -177,11 +349,89 @@ mod tests {
         assert_eq!(&chunk.to_string(), "RuSt");
     }
 
-    // #[test]
-    // pub fn test_chunk_type_trait_impls() {
-    //     let chunk_type_1: ChunkType = TryFrom::try_from([82, 117, 83, 116]).unwrap();
-    //     let chunk_type_2: ChunkType = FromStr::from_str("RuSt").unwrap();
-    //     let _chunk_string = format!("{}", chunk_type_1);
-    //     let _are_chunks_equal = chunk_type_1 == chunk_type_2;
-    // }
+    #[test]
+    pub fn test_classify_critical_known() {
+        let chunk = ChunkType::from_str("IHDR").unwrap();
+        assert_eq!(
+            chunk.classify(),
+            ChunkClass::Critical(StandardChunkType::Ihdr)
+        );
+    }
+
+    #[test]
+    pub fn test_classify_ancillary_known() {
+        let chunk = ChunkType::from_str("tEXt").unwrap();
+        assert_eq!(
+            chunk.classify(),
+            ChunkClass::Ancillary(StandardChunkType::Text)
+        );
+    }
+
+    #[test]
+    pub fn test_classify_unknown() {
+        let chunk = ChunkType::from_str("RuSt").unwrap();
+        assert_eq!(chunk.classify(), ChunkClass::Unknown);
+    }
+
+    #[test]
+    pub fn test_named_constant_matches_parse() {
+        assert_eq!(ChunkType::IEND, ChunkType::from_str("IEND").unwrap());
+    }
+
+    #[test]
+    pub fn test_standard_chunk_is_textual() {
+        assert!(StandardChunkType::Text.is_textual());
+        assert!(!StandardChunkType::Ihdr.is_textual());
+    }
+
+    #[test]
+    pub fn test_decode_text() {
+        let mut data: Vec<u8> = b"Author".to_vec();
+        data.push(0);
+        data.extend_from_slice(b"Ada Lovelace");
+        let (keyword, value) = decode_text(&data).unwrap();
+        assert_eq!(keyword, "Author");
+        assert_eq!(value, "Ada Lovelace");
+    }
+
+    #[test]
+    pub fn test_decode_ztxt() {
+        // "hello" compressed with zlib (fixed Huffman), keyword "Comment".
+        let mut data: Vec<u8> = b"Comment".to_vec();
+        data.push(0);
+        data.push(0); // compression method: zlib
+        data.extend_from_slice(&[
+            0x78, 0x9c, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00, 0x06, 0x2c, 0x02, 0x15,
+        ]);
+        let (keyword, value) = decode_ztxt(&data).unwrap();
+        assert_eq!(keyword, "Comment");
+        assert_eq!(value, "hello");
+    }
+
+    #[test]
+    pub fn test_chunk_type_trait_impls() {
+        let chunk_type_1: ChunkType = TryFrom::try_from([82, 117, 83, 116]).unwrap();
+        let chunk_type_2: ChunkType = FromStr::from_str("RuSt").unwrap();
+        let _chunk_string = format!("{}", chunk_type_1);
+        let _are_chunks_equal = chunk_type_1 == chunk_type_2;
+    }
+
+    #[test]
+    pub fn test_from_str_rejects_wrong_length() {
+        assert_eq!(
+            ChunkType::from_str("RuStY"),
+            Err(ChunkTypeError::InvalidLength { got: 5 })
+        );
+    }
+
+    #[test]
+    pub fn test_try_from_reports_offending_byte() {
+        assert_eq!(
+            ChunkType::try_from([82, 117, 49, 116]),
+            Err(ChunkTypeError::InvalidByte {
+                byte: 49,
+                position: 2
+            })
+        );
+    }
 }
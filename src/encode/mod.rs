@@ -0,0 +1,104 @@
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::png::Png;
+use std::str::FromStr;
+
+/// Hides a message inside a PNG by inserting a private, ancillary,
+/// safe-to-copy chunk of the given type just before the `IEND` chunk.
+///
+/// The chunk type the caller picks (for example `ruSt`) is private and
+/// ancillary with its safe-to-copy bit set, so conformant decoders ignore
+/// the data while it survives copying — a simple watermark/secret-message.
+pub fn encode(png: &mut Png, chunk_type: &str, message: &str) -> Result<(), String> {
+    let chunk_type = ChunkType::from_str(chunk_type)?;
+    if chunk_type.is_critical() || chunk_type.is_public() || !chunk_type.is_safe_to_copy() {
+        return Err(format!(
+            "Chunk type {} must be private, ancillary and safe to copy",
+            chunk_type
+        ));
+    }
+    let chunk = Chunk::new(chunk_type, message.as_bytes().to_vec());
+
+    // Keep the message chunk ahead of IEND: lift IEND out, append the
+    // payload, then put IEND back as the final chunk.
+    let iend = png.remove_first_chunk("IEND")?;
+    png.append_chunk(chunk);
+    png.append_chunk(iend);
+    Ok(())
+}
+
+/// Recovers the message previously hidden in the first chunk of the
+/// given type.
+pub fn decode(png: &Png, chunk_type: &str) -> Result<String, String> {
+    match png.chunk_by_type(chunk_type) {
+        Some(chunk) => chunk.data_as_string(),
+        None => Err(format!("No chunk of type {} found", chunk_type)),
+    }
+}
+
+/// Removes the first chunk of the given type, discarding its message.
+pub fn remove(png: &mut Png, chunk_type: &str) -> Result<(), String> {
+    png.remove_first_chunk(chunk_type)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+    use crate::png::Png;
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Chunk {
+        let chunk_type = ChunkType::from_str(chunk_type).unwrap();
+        Chunk::new(chunk_type, data.bytes().collect())
+    }
+
+    fn testing_png() -> Png {
+        Png::from_chunks(vec![
+            chunk_from_strings("IHDR", "I am the first chunk"),
+            chunk_from_strings("IDAT", "I am another chunk"),
+            chunk_from_strings("IEND", ""),
+        ])
+    }
+
+    #[test]
+    pub fn test_encode_inserts_before_iend() {
+        let mut png = testing_png();
+        encode(&mut png, "ruSt", "secret").unwrap();
+        let last = png.chunks().last().unwrap();
+        assert_eq!(last.chunk_type().to_string(), "IEND");
+        assert_eq!(png.chunks().len(), 4);
+    }
+
+    #[test]
+    pub fn test_encode_chunk_is_ancillary_and_safe_to_copy() {
+        let mut png = testing_png();
+        encode(&mut png, "ruSt", "secret").unwrap();
+        let chunk = png.chunk_by_type("ruSt").unwrap();
+        assert!(!chunk.chunk_type().is_critical());
+        assert!(!chunk.chunk_type().is_public());
+        assert!(chunk.chunk_type().is_safe_to_copy());
+    }
+
+    #[test]
+    pub fn test_decode_round_trip() {
+        let mut png = testing_png();
+        encode(&mut png, "ruSt", "a hidden message").unwrap();
+        assert_eq!(decode(&png, "ruSt").unwrap(), "a hidden message");
+    }
+
+    #[test]
+    pub fn test_decode_missing() {
+        let png = testing_png();
+        assert!(decode(&png, "ruSt").is_err());
+    }
+
+    #[test]
+    pub fn test_remove() {
+        let mut png = testing_png();
+        encode(&mut png, "ruSt", "secret").unwrap();
+        remove(&mut png, "ruSt").unwrap();
+        assert!(decode(&png, "ruSt").is_err());
+    }
+}
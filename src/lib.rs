@@ -0,0 +1,6 @@
+pub mod apng;
+pub mod chunk;
+pub mod chunk_type;
+pub mod encode;
+pub mod png;
+pub mod zlib;
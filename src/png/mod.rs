@@ -0,0 +1,173 @@
+use crate::chunk::Chunk;
+use std::convert::TryFrom;
+
+/// An entire PNG image modelled as its 8-byte signature followed by an
+/// ordered list of chunks.
+#[derive(Debug)]
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+/// methods
+impl Png {
+    /// The 8 bytes every PNG stream must start with.
+    pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    /// Builds a `Png` from an in-memory list of chunks.
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Png {
+        Png { chunks }
+    }
+
+    /// Parses a PNG from raw bytes, validating the signature and reading
+    /// chunks until (and including) the terminating `IEND` chunk.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Png, String> {
+        if bytes.len() < 8 {
+            return Err("PNG is shorter than its 8-byte signature".to_string());
+        }
+        if bytes[0..8] != Png::STANDARD_HEADER {
+            return Err("PNG signature is invalid".to_string());
+        }
+
+        let mut chunks: Vec<Chunk> = Vec::new();
+        let mut offset = 8;
+        loop {
+            let chunk = Chunk::try_from(&bytes[offset..])?;
+            offset += 12 + chunk.length() as usize;
+            let is_end = chunk.chunk_type().to_string() == "IEND";
+            chunks.push(chunk);
+            if is_end {
+                break;
+            }
+            if offset >= bytes.len() {
+                return Err("PNG ended before an IEND chunk was found".to_string());
+            }
+        }
+
+        Ok(Png { chunks })
+    }
+
+    /// Appends a chunk to the end of the chunk list.
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    /// Removes and returns the first chunk matching the given type.
+    pub fn remove_first_chunk(&mut self, chunk_type: &str) -> Result<Chunk, String> {
+        match self
+            .chunks
+            .iter()
+            .position(|chunk| chunk.chunk_type().to_string() == chunk_type)
+        {
+            Some(index) => Ok(self.chunks.remove(index)),
+            None => Err(format!("No chunk of type {} found", chunk_type)),
+        }
+    }
+
+    /// The 8-byte PNG signature.
+    pub fn header(&self) -> &[u8; 8] {
+        &Png::STANDARD_HEADER
+    }
+
+    /// All chunks in order.
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    /// Returns the first chunk matching the given type, if any.
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.chunk_type().to_string() == chunk_type)
+    }
+
+    /// Serializes the PNG back to its signature followed by every chunk.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        bytes.extend_from_slice(&Png::STANDARD_HEADER);
+        for chunk in &self.chunks {
+            bytes.extend_from_slice(&chunk.as_bytes());
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Chunk {
+        let chunk_type = ChunkType::from_str(chunk_type).unwrap();
+        let data: Vec<u8> = data.bytes().collect();
+        Chunk::new(chunk_type, data)
+    }
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("IHDR", "I am the first chunk"),
+            chunk_from_strings("IDAT", "I am another chunk"),
+            chunk_from_strings("IEND", ""),
+        ]
+    }
+
+    fn testing_png() -> Png {
+        Png::from_chunks(testing_chunks())
+    }
+
+    #[test]
+    pub fn test_from_chunks() {
+        let chunks = testing_chunks();
+        let png = Png::from_chunks(chunks);
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    pub fn test_valid_from_bytes() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+        let parsed = Png::from_bytes(bytes.as_ref()).unwrap();
+        assert_eq!(parsed.chunks().len(), 3);
+    }
+
+    #[test]
+    pub fn test_invalid_header() {
+        let mut bytes = testing_png().as_bytes();
+        bytes[0] = 0;
+        assert!(Png::from_bytes(bytes.as_ref()).is_err());
+    }
+
+    #[test]
+    pub fn test_append_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message"));
+        let chunk = png.chunk_by_type("TeSt").unwrap();
+        assert_eq!(chunk.chunk_type().to_string(), "TeSt");
+        assert_eq!(chunk.data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    pub fn test_remove_first_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message"));
+        png.remove_first_chunk("TeSt").unwrap();
+        let chunk = png.chunk_by_type("TeSt");
+        assert!(chunk.is_none());
+    }
+
+    #[test]
+    pub fn test_chunk_by_type() {
+        let png = testing_png();
+        let chunk = png.chunk_by_type("IDAT").unwrap();
+        assert_eq!(chunk.chunk_type().to_string(), "IDAT");
+    }
+
+    #[test]
+    pub fn test_as_bytes_round_trip() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+        let parsed = Png::from_bytes(bytes.as_ref()).unwrap();
+        assert_eq!(bytes, parsed.as_bytes());
+    }
+}
@@ -0,0 +1,312 @@
+//! A small, dependency-free zlib/DEFLATE decompressor.
+//!
+//! PNG stores `zTXt` (and `iCCP`) payloads as zlib streams. Rather than
+//! pull in an external crate — the rest of this crate hand-rolls its
+//! primitives (see the CRC-32 in `chunk`) — this implements just enough
+//! of RFC 1950/1951 to inflate those streams.
+
+/// Reads individual bits out of a byte slice, least-significant bit first,
+/// as DEFLATE requires.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    byte_pos: usize,
+    bit_pos: u8,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> BitReader<'a> {
+        BitReader {
+            bytes,
+            byte_pos: 0,
+            bit_pos: 0,
+        }
+    }
+
+    /// Reads a single bit.
+    fn bit(&mut self) -> Result<u32, String> {
+        if self.byte_pos >= self.bytes.len() {
+            return Err("Unexpected end of deflate stream".to_string());
+        }
+        let bit = (self.bytes[self.byte_pos] >> self.bit_pos) & 1;
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+        Ok(bit as u32)
+    }
+
+    /// Reads `count` bits, least-significant bit first.
+    fn bits(&mut self, count: u8) -> Result<u32, String> {
+        let mut value = 0u32;
+        for i in 0..count {
+            value |= self.bit()? << i;
+        }
+        Ok(value)
+    }
+
+    /// Discards any partially consumed byte, aligning to a byte boundary.
+    fn align(&mut self) {
+        if self.bit_pos != 0 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+    }
+}
+
+/// A canonical Huffman decoder built from a list of code lengths.
+struct Huffman {
+    // counts[len] = number of codes of that bit-length.
+    counts: Vec<u16>,
+    // symbols ordered by (length, symbol value).
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let max_len = lengths.iter().copied().max().unwrap_or(0) as usize;
+        let mut counts = vec![0u16; max_len + 1];
+        for &len in lengths {
+            if len != 0 {
+                counts[len as usize] += 1;
+            }
+        }
+
+        // Starting offset of each length group within `symbols`.
+        let mut offsets = vec![0u16; max_len + 2];
+        for len in 1..=max_len {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                let slot = offsets[len as usize] as usize;
+                symbols[slot] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+
+        Huffman { counts, symbols }
+    }
+
+    /// Decodes the next symbol from the bit stream.
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, String> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for len in 1..self.counts.len() {
+            code |= reader.bit()? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + (code - first)) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err("Invalid Huffman code in deflate stream".to_string())
+    }
+}
+
+// Length codes 257..=285: base length and extra bits.
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+// Distance codes 0..=29: base distance and extra bits.
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// Order in which code-length-code lengths appear in dynamic blocks.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Inflates a raw DEFLATE stream (RFC 1951).
+fn inflate(reader: &mut BitReader) -> Result<Vec<u8>, String> {
+    let mut out: Vec<u8> = Vec::new();
+    loop {
+        let final_block = reader.bit()? == 1;
+        let block_type = reader.bits(2)?;
+        match block_type {
+            0 => inflate_stored(reader, &mut out)?,
+            1 => inflate_block(reader, &mut out, &fixed_literal_tree(), &fixed_distance_tree())?,
+            2 => {
+                let (lit, dist) = read_dynamic_trees(reader)?;
+                inflate_block(reader, &mut out, &lit, &dist)?;
+            }
+            _ => return Err("Invalid deflate block type".to_string()),
+        }
+        if final_block {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+/// Copies a stored (uncompressed) block.
+fn inflate_stored(reader: &mut BitReader, out: &mut Vec<u8>) -> Result<(), String> {
+    reader.align();
+    if reader.byte_pos + 4 > reader.bytes.len() {
+        return Err("Truncated stored block header".to_string());
+    }
+    let len = reader.bytes[reader.byte_pos] as usize
+        | ((reader.bytes[reader.byte_pos + 1] as usize) << 8);
+    reader.byte_pos += 4; // skip LEN and its one's-complement NLEN
+    if reader.byte_pos + len > reader.bytes.len() {
+        return Err("Truncated stored block body".to_string());
+    }
+    out.extend_from_slice(&reader.bytes[reader.byte_pos..reader.byte_pos + len]);
+    reader.byte_pos += len;
+    Ok(())
+}
+
+/// Inflates a Huffman-coded block given its literal/length and distance trees.
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), String> {
+    loop {
+        let symbol = literals.decode(reader)?;
+        if symbol == 256 {
+            break;
+        } else if symbol < 256 {
+            out.push(symbol as u8);
+        } else {
+            let index = (symbol - 257) as usize;
+            if index >= LENGTH_BASE.len() {
+                return Err("Invalid length symbol in deflate stream".to_string());
+            }
+            let length =
+                LENGTH_BASE[index] as usize + reader.bits(LENGTH_EXTRA[index])? as usize;
+            let dist_symbol = distances.decode(reader)? as usize;
+            if dist_symbol >= DIST_BASE.len() {
+                return Err("Invalid distance symbol in deflate stream".to_string());
+            }
+            let distance =
+                DIST_BASE[dist_symbol] as usize + reader.bits(DIST_EXTRA[dist_symbol])? as usize;
+            if distance > out.len() {
+                return Err("Distance points before start of output".to_string());
+            }
+            let start = out.len() - distance;
+            for i in 0..length {
+                out.push(out[start + i]);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The fixed literal/length Huffman tree defined by RFC 1951 §3.2.6.
+fn fixed_literal_tree() -> Huffman {
+    let mut lengths = [0u8; 288];
+    for (i, len) in lengths.iter_mut().enumerate() {
+        *len = match i {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+    Huffman::new(&lengths)
+}
+
+/// The fixed distance tree: 30 codes of 5 bits each.
+fn fixed_distance_tree() -> Huffman {
+    Huffman::new(&[5u8; 30])
+}
+
+/// Reads the dynamic literal and distance trees that prefix a type-2 block.
+fn read_dynamic_trees(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let hlit = reader.bits(5)? as usize + 257;
+    let hdist = reader.bits(5)? as usize + 1;
+    let hclen = reader.bits(4)? as usize + 4;
+
+    let mut code_length_lengths = [0u8; 19];
+    for i in 0..hclen {
+        code_length_lengths[CODE_LENGTH_ORDER[i]] = reader.bits(3)? as u8;
+    }
+    let code_length_tree = Huffman::new(&code_length_lengths);
+
+    let mut lengths: Vec<u8> = Vec::with_capacity(hlit + hdist);
+    while lengths.len() < hlit + hdist {
+        let symbol = code_length_tree.decode(reader)?;
+        match symbol {
+            0..=15 => lengths.push(symbol as u8),
+            16 => {
+                let repeat = reader.bits(2)? as usize + 3;
+                let prev = *lengths
+                    .last()
+                    .ok_or("Repeat code with no previous length")?;
+                lengths.resize(lengths.len() + repeat, prev);
+            }
+            17 => {
+                let repeat = reader.bits(3)? as usize + 3;
+                lengths.resize(lengths.len() + repeat, 0);
+            }
+            18 => {
+                let repeat = reader.bits(7)? as usize + 11;
+                lengths.resize(lengths.len() + repeat, 0);
+            }
+            _ => return Err("Invalid code-length symbol in deflate stream".to_string()),
+        }
+    }
+
+    let literal_tree = Huffman::new(&lengths[..hlit]);
+    let distance_tree = Huffman::new(&lengths[hlit..hlit + hdist]);
+    Ok((literal_tree, distance_tree))
+}
+
+/// Decompresses a zlib stream (RFC 1950): a 2-byte header, a raw DEFLATE
+/// body, and a trailing Adler-32 checksum which this ignores.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < 2 {
+        return Err("zlib stream is too short".to_string());
+    }
+    let cmf = data[0];
+    if cmf & 0x0F != 8 {
+        return Err("Unsupported zlib compression method".to_string());
+    }
+    let mut reader = BitReader::new(&data[2..]);
+    inflate(&mut reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_decompress_stored() {
+        // zlib header (0x78 0x01) + a single final stored block holding "Hi".
+        let stream = [0x78, 0x01, 0x01, 0x02, 0x00, 0xFD, 0xFF, b'H', b'i'];
+        let out = decompress(&stream).unwrap();
+        assert_eq!(out, b"Hi");
+    }
+
+    #[test]
+    pub fn test_decompress_fixed() {
+        // "hello" compressed with zlib at default settings (fixed Huffman).
+        let stream = [
+            0x78, 0x9c, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00, 0x06, 0x2c, 0x02, 0x15,
+        ];
+        let out = decompress(&stream).unwrap();
+        assert_eq!(out, b"hello");
+    }
+
+    #[test]
+    pub fn test_unsupported_method() {
+        assert!(decompress(&[0x79, 0x01]).is_err());
+    }
+}